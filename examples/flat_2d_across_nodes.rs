@@ -13,7 +13,6 @@ fn main() {
         .add_plugins(DefaultPlugins)
         .add_plugin(NavigationPlugin)
         .init_resource::<ButtonMaterials>()
-        .init_resource::<InputMapping>()
         .add_startup_system(setup)
         .add_system(button_system)
         .add_system(print_nav_events)
@@ -68,6 +67,10 @@ fn button_system(
 }
 
 fn setup(mut commands: Commands, button_materials: Res<ButtonMaterials>) {
+    // `InputMapping` is a component rather than a resource so several can
+    // coexist, one per local player; a single-player game just needs one.
+    commands.spawn().insert(InputMapping::default());
+
     let size = |width, height| Size::new(Val::Percent(width), Val::Percent(height));
     let flex_wrap = FlexWrap::Wrap;
     let style = Style {
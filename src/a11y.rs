@@ -0,0 +1,90 @@
+//! Optional bridge between this crate's focus state and `bevy_a11y` /
+//! AccessKit, so navigable UIs stay usable with screen readers.
+//!
+//! Gated behind the `a11y` feature: add [`AccessibleName`] to a [`Focusable`]
+//! for assistive tech to announce it, and add [`sync_focus_to_a11y`] /
+//! [`accessibility_requests_to_nav`] to your app (done automatically by
+//! [`crate::NavigationPlugin`] when the feature is enabled).
+use bevy::prelude::*;
+use bevy_a11y::{
+    accesskit::{NodeBuilder, Role},
+    AccessibilityNode, Focus as A11yFocus,
+};
+
+use crate::events::{FocusSource, NavRequest};
+use crate::{Focusable, Focused, PlayerId};
+
+/// An accessible name (and optionally role) for a [`Focusable`], read by
+/// [`sync_focus_to_a11y`] to build the `AccessibilityNode` announced to
+/// assistive technology.
+#[derive(Clone, Debug)]
+pub struct AccessibleName {
+    /// The name assistive technology announces for this node.
+    pub name: String,
+    /// The AccessKit role this node is exposed as.
+    pub role: Role,
+}
+impl AccessibleName {
+    /// A named accessible node with the default [`Role::Button`].
+    pub fn new(name: impl Into<String>) -> Self {
+        AccessibleName {
+            name: name.into(),
+            role: Role::Button,
+        }
+    }
+}
+
+/// Mirrors this crate's [`Focused`] component into `bevy_a11y`'s focus
+/// resource, and attaches an `AccessibilityNode` built from [`AccessibleName`]
+/// so assistive technology announces the focused node.
+///
+/// `bevy_a11y` only tracks one focus at a time, so in a multiplayer
+/// ([`PlayerId`]) setup only [`PlayerId::default`]'s cursor is mirrored; other
+/// players' focus changes are not announced.
+pub fn sync_focus_to_a11y(
+    mut commands: Commands,
+    mut a11y_focus: ResMut<A11yFocus>,
+    newly_focused: Query<(Entity, &Focused, Option<&AccessibleName>), (With<Focusable>, Added<Focused>)>,
+) {
+    for (entity, focused, name) in newly_focused.iter() {
+        if focused.0 != PlayerId::default() {
+            continue;
+        }
+        a11y_focus.0 = Some(entity);
+        if let Some(name) = name {
+            let mut node = NodeBuilder::new(name.role);
+            node.set_name(name.name.clone());
+            commands.entity(entity).insert(AccessibilityNode(node));
+        }
+    }
+}
+
+/// Converts platform accessibility focus requests (eg. a screen reader user
+/// tabbing through the accessibility tree) into [`NavRequest::FocusOn`], so
+/// they drive this crate's focus system too.
+pub fn accessibility_requests_to_nav(
+    a11y_focus: Res<A11yFocus>,
+    mut nav_cmds: EventWriter<NavRequest>,
+    focused: Query<&Focused>,
+    mut last_requested: Local<Option<Entity>>,
+) {
+    if *last_requested == a11y_focus.0 {
+        return;
+    }
+    *last_requested = a11y_focus.0;
+    if let Some(entity) = a11y_focus.0 {
+        // Already focused: this is `sync_focus_to_a11y` mirroring our own
+        // focus, not a platform request.
+        if focused
+            .get(entity)
+            .map_or(false, |focused| focused.0 == PlayerId::default())
+        {
+            return;
+        }
+        nav_cmds.send(NavRequest::FocusOn(
+            PlayerId::default(),
+            entity,
+            FocusSource::Programmatic,
+        ));
+    }
+}
@@ -0,0 +1,75 @@
+//! Requests you send to drive navigation, and why it happened.
+use bevy::prelude::Entity;
+
+use crate::PlayerId;
+
+/// The four cardinal directions navigation can move in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+    /// Move up.
+    North,
+    /// Move down.
+    South,
+    /// Move right.
+    East,
+    /// Move left.
+    West,
+}
+impl Direction {
+    /// The movement [`crate::Axis`] (horizontal/vertical) this direction lies on.
+    pub fn axis(self) -> crate::Axis {
+        match self {
+            Direction::North | Direction::South => crate::Axis::Vertical,
+            Direction::East | Direction::West => crate::Axis::Horizontal,
+        }
+    }
+}
+
+/// Which way to step within a scoped menu (eg. tabs), see [`NavRequest::ScopeMove`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ScopeDirection {
+    /// Step to the next element.
+    Next,
+    /// Step to the previous element.
+    Previous,
+}
+
+/// Why a focus change happened, attached to the focus-change variants of
+/// [`crate::NavEvent`].
+///
+/// This lets consumers distinguish, for example, a mouse hover from a
+/// gamepad `Move` when deciding whether to play a "focus moved" sound.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FocusSource {
+    /// Focus changed because a pointer (mouse or touch) hovered a node.
+    Pointer,
+    /// Focus changed in response to a directional [`NavRequest::Move`].
+    Directional,
+    /// Focus changed in response to a [`NavRequest::ScopeMove`].
+    Scope,
+    /// Focus changed because user code sent a [`NavRequest::FocusOn`] directly.
+    Programmatic,
+}
+
+/// A request sent to the navigation system to move, scroll or activate focus.
+///
+/// Every variant carries the [`PlayerId`] whose cursor it concerns, so
+/// several players can each drive their own focus independently; a
+/// single-player game can just always use `PlayerId::default()`.
+///
+/// Send these yourself, or use the `default_*_input` systems in
+/// [`crate::systems`] to translate keyboard/gamepad/mouse input into
+/// requests automatically.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum NavRequest {
+    /// Move in a given direction relative to the currently focused element.
+    Move(PlayerId, Direction),
+    /// Cycle through a scoped menu (eg. tabs) in a given direction.
+    ScopeMove(PlayerId, ScopeDirection),
+    /// Activate the currently focused element.
+    Action(PlayerId),
+    /// Cancel out of the currently focused element (eg. close a submenu).
+    Cancel(PlayerId),
+    /// Focus a specific entity directly, tagged with why it was requested.
+    FocusOn(PlayerId, Entity, FocusSource),
+}
@@ -0,0 +1,224 @@
+//! Semi-automatic, spatial navigation for bevy UI.
+//!
+//! Add [`NavigationPlugin`], mark entities you want to be able to focus with
+//! [`Focusable`], wall off independently-navigable regions with [`NavMenu`],
+//! then feed the system [`events::NavRequest`]s (see [`systems`] for
+//! ready-made keyboard/gamepad/mouse input systems) and react to
+//! [`NavEvent`]s.
+#[cfg(feature = "a11y")]
+pub mod a11y;
+pub mod events;
+pub mod systems;
+
+use bevy::ecs::system::Query;
+use bevy::hierarchy::Parent;
+use bevy::prelude::*;
+
+use events::{Direction, FocusSource, NavRequest, ScopeDirection};
+
+/// Identifies which player's focus cursor a [`NavRequest`] or [`NavEvent`]
+/// concerns.
+///
+/// Defaults to `PlayerId(0)`, the only player in a single-player game. For
+/// local co-op, give each [`systems::InputMapping`] a distinct `PlayerId` (and
+/// gamepad) so each player drives their own [`Focused`] cursor independently.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct PlayerId(pub u32);
+
+/// A horizontal or vertical movement axis, see [`Focusable::lock_axis`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Axis {
+    /// East/West.
+    Horizontal,
+    /// North/South.
+    Vertical,
+}
+
+/// Component marking an entity that can receive focus.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Focusable {
+    pub(crate) adjust_lock: Option<Axis>,
+}
+impl Focusable {
+    /// Lock `axis` for this focusable: while it is focused, a
+    /// [`NavRequest::Move`] along that axis is *not* resolved into a focus
+    /// change, and instead emits [`NavEvent::Adjusted`]. Movement on the
+    /// orthogonal axis still navigates normally.
+    ///
+    /// Useful for widgets like sliders, steppers and tab-pickers where
+    /// left/right should adjust a value rather than move focus away.
+    pub fn lock_axis(axis: Axis) -> Self {
+        Focusable {
+            adjust_lock: Some(axis),
+        }
+    }
+}
+
+/// Marker component for the [`Focusable`] currently focused by a given
+/// player, within its enclosing [`NavMenu`].
+///
+/// Several `Focused` entities (one per [`PlayerId`]) may coexist, each
+/// independently navigated.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Focused(pub PlayerId);
+
+/// Which axes a [`NavMenu`] wraps navigation on, see [`NavMenu::wrapping`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NavWrap {
+    /// Wrap East/West movement that dead-ends at the menu's edge.
+    pub horizontal: bool,
+    /// Wrap North/South movement that dead-ends at the menu's edge.
+    pub vertical: bool,
+}
+
+/// Marks an entity (and its descendants) as a navigation boundary.
+///
+/// [`Focusable`]s are only reachable from each other through directional or
+/// scope navigation if they share the same nearest enclosing `NavMenu`.
+#[derive(Clone, Copy, Debug)]
+pub struct NavMenu {
+    pub(crate) wrap: NavWrap,
+    pub(crate) player: Option<PlayerId>,
+}
+impl NavMenu {
+    /// Create a top-level `NavMenu`, the root of a navigable UI tree.
+    ///
+    /// Every `Focusable` must have exactly one enclosing `NavMenu`, so an
+    /// englobing root `NavMenu` is required even for a UI with no nested
+    /// menus.
+    pub fn root() -> Self {
+        NavMenu {
+            wrap: NavWrap::default(),
+            player: None,
+        }
+    }
+
+    /// Opt into edge-wrapping: a [`events::NavRequest::Move`] that finds no
+    /// focusable neighbor in the requested direction instead wraps to the
+    /// farthest focusable on the opposite side of this menu, along the
+    /// movement axis.
+    pub fn wrapping(mut self) -> Self {
+        self.wrap = NavWrap {
+            horizontal: true,
+            vertical: true,
+        };
+        self
+    }
+
+    /// Restrict this menu (and its descendants) to `player`'s cursor, so
+    /// [`systems::resolve_focus`] grants `player` its own starting focusable
+    /// here instead of fighting other players over a shared one.
+    ///
+    /// Useful for local multiplayer: give each player their own `for_player`
+    /// root `NavMenu`.
+    pub fn for_player(mut self, player: PlayerId) -> Self {
+        self.player = Some(player);
+        self
+    }
+}
+
+/// Events emitted by [`systems::resolve_focus`] in reaction to [`NavRequest`]s.
+///
+/// Every variant carries the [`PlayerId`] of the cursor it concerns, so a
+/// multiplayer game can tell which player's focus changed.
+#[derive(Clone, Debug)]
+pub enum NavEvent {
+    /// Focus moved from one focusable to another.
+    FocusChanged {
+        /// Which player's cursor moved.
+        player: PlayerId,
+        /// The previously focused entity, or the same entity as `to` when
+        /// the player had no focus before.
+        from: Entity,
+        /// The newly focused entity.
+        to: Entity,
+        /// Why the focus changed.
+        source: FocusSource,
+    },
+    /// The currently focused element was activated with [`NavRequest::Action`].
+    FocusActivated(PlayerId, Entity),
+    /// A [`NavRequest`] could not be fulfilled, eg. there was no focusable
+    /// neighbor in the requested direction.
+    NoChanges {
+        /// Which player's request could not be fulfilled.
+        player: PlayerId,
+        /// The entity that stayed focused.
+        from: Entity,
+        /// The request that could not be fulfilled.
+        request: NavRequest,
+    },
+    /// A [`Focusable::lock_axis`] focusable consumed a directional
+    /// [`NavRequest::Move`] as a value adjustment instead of losing focus.
+    Adjusted {
+        /// Which player's cursor triggered the adjustment.
+        player: PlayerId,
+        /// The focusable that was adjusted.
+        entity: Entity,
+        /// The direction requested.
+        direction: Direction,
+    },
+}
+
+/// Bevy plugin to enable ui navigation.
+///
+/// Adds the [`NavRequest`]/[`NavEvent`] events and the focus resolution
+/// system. You still need to add an input system (see [`systems`]) yourself
+/// that sends [`NavRequest`]s.
+pub struct NavigationPlugin;
+impl Plugin for NavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<systems::NavRepeat>()
+            .add_event::<NavRequest>()
+            .add_event::<NavEvent>()
+            .add_system(systems::resolve_focus);
+        #[cfg(feature = "a11y")]
+        app.add_system(a11y::sync_focus_to_a11y)
+            .add_system(a11y::accessibility_requests_to_nav);
+    }
+}
+
+/// Returns the item of `iter` that maximizes `key`, if any.
+pub(crate) fn max_by_in_iter<I, T>(iter: I, key: impl Fn(&T) -> f32) -> Option<T>
+where
+    I: Iterator<Item = T>,
+{
+    iter.fold(None, |acc, item| match acc {
+        Some(ref acc_item) if key(acc_item) >= key(&item) => acc,
+        _ => Some(item),
+    })
+}
+
+/// Find the entity of the nearest enclosing [`NavMenu`] above (not
+/// including) `entity`, if any.
+pub(crate) fn enclosing_menu(
+    entity: Entity,
+    parents: &Query<&Parent>,
+    menus: &Query<&NavMenu>,
+) -> Option<Entity> {
+    let mut current = entity;
+    loop {
+        current = parents.get(current).ok()?.0;
+        if menus.get(current).is_ok() {
+            return Some(current);
+        }
+    }
+}
+
+/// Walks the [`NavMenu`] ancestors of `entity`, returning the nearest one's
+/// restriction to a single player (see [`NavMenu::for_player`]), if any menu
+/// in the chain has one.
+pub(crate) fn menu_player(
+    entity: Entity,
+    parents: &Query<&Parent>,
+    menus: &Query<&NavMenu>,
+) -> Option<PlayerId> {
+    let mut current = entity;
+    loop {
+        current = parents.get(current).ok()?.0;
+        if let Ok(menu) = menus.get(current) {
+            if menu.player.is_some() {
+                return menu.player;
+            }
+        }
+    }
+}
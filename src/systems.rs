@@ -1,14 +1,24 @@
 //! System for the navigation tree and default input systems to get started
-use crate::events::{Direction, NavRequest, ScopeDirection};
-use crate::{max_by_in_iter, Focusable, Focused};
+use crate::events::{Direction, FocusSource, NavRequest, ScopeDirection};
+use crate::{
+    enclosing_menu, max_by_in_iter, menu_player, Focusable, Focused, NavEvent, NavMenu, PlayerId,
+};
 use bevy::ecs::system::SystemParam;
+use bevy::hierarchy::Parent;
 use bevy::math::Vec3Swizzles;
 use bevy::prelude::*;
 use bevy::ui::camera::CAMERA_UI;
 use bevy::render::camera::Camera;
+use std::collections::HashMap;
 
 /// Control default ui navigation input buttons
 pub struct InputMapping {
+    /// Which player this input mapping drives; lets several `InputMapping`s
+    /// (eg. one per gamepad) coexist for local multiplayer.
+    pub player: PlayerId,
+    /// The gamepad this mapping reads from, or `None` to use the first
+    /// connected gamepad.
+    pub gamepad: Option<Gamepad>,
     /// Deadzone on the gamepad left stick for ui navigation
     pub joystick_ui_deadzone: f32,
     /// X axis of gamepad stick
@@ -56,9 +66,36 @@ pub struct InputMapping {
     /// Mouse button for [`NavRequest::Action`]
     pub mouse_action: MouseButton,
 }
+/// Controls the auto-repeat behavior of held directional navigation inputs.
+///
+/// When a movement key/axis is held past `first_delay`, [`default_keyboard_input`]
+/// and [`default_gamepad_input`] keep sending [`NavRequest::Move`] every
+/// `repeat_interval`, accelerating toward `min_interval` on each repeat.
+pub struct NavRepeat {
+    /// Delay before the first repeated move, in seconds.
+    pub first_delay: f32,
+    /// Delay between repeated moves, in seconds.
+    pub repeat_interval: f32,
+    /// The interval repetition accelerates toward, in seconds.
+    pub min_interval: f32,
+}
+impl Default for NavRepeat {
+    fn default() -> Self {
+        NavRepeat {
+            first_delay: 0.6,
+            repeat_interval: 0.15,
+            min_interval: 0.05,
+        }
+    }
+}
+/// How much `repeat_interval` shrinks toward `min_interval` on each repeat.
+const REPEAT_ACCEL: f32 = 0.85;
+
 impl Default for InputMapping {
     fn default() -> Self {
         InputMapping {
+            player: PlayerId::default(),
+            gamepad: None,
             joystick_ui_deadzone: 0.36,
             move_x: GamepadAxisType::LeftStickX,
             move_y: GamepadAxisType::LeftStickY,
@@ -91,99 +128,197 @@ macro_rules! mapping {
     ($($from:expr => $to:expr),* ) => ([$( ( $from, $to ) ),*])
 }
 
+/// Tracks a held directional gamepad input to drive auto-repeat.
+#[derive(Default)]
+struct GamepadHeldDirection {
+    direction: Option<Direction>,
+    timer: f32,
+    interval: f32,
+}
+
 /// A system to send gamepad control events to the focus system
 ///
 /// Dpad and left stick for movement, `LT` and `RT` for scopped menus, `A` `B`
-/// for selection and cancel.
+/// for selection and cancel. Holding a direction past [`NavRepeat::first_delay`]
+/// keeps sending [`NavRequest::Move`] every [`NavRepeat::repeat_interval`],
+/// accelerating toward [`NavRepeat::min_interval`] on each repeat.
 ///
-/// The button mapping may be controlled through the [`InputMapping`] resource.
-/// You may however need to customize the behavior of this system (typically
-/// when integrating in the game) in this case, you should write your own
-/// system that sends [`NavRequest`](crate::NavRequest) events
+/// The button mapping, target gamepad and player may be controlled through
+/// each [`InputMapping`]; add one such component per local player
+/// (see [`InputMapping::gamepad`] and [`InputMapping::player`]) to support
+/// couch co-op. You may however need to customize the behavior of this
+/// system (typically when integrating in the game) in this case, you should
+/// write your own system that sends [`NavRequest`](crate::NavRequest) events
 pub fn default_gamepad_input(
     mut nav_cmds: EventWriter<NavRequest>,
-    input_mapping: Res<InputMapping>,
+    input_mappings: Query<&InputMapping>,
+    nav_repeat: Res<NavRepeat>,
+    time: Res<Time>,
     buttons: Res<Input<GamepadButton>>,
     axis: Res<Axis<GamepadAxis>>,
-    mut ui_input_status: Local<bool>,
+    gamepads: Res<Gamepads>,
+    mut held: Local<HashMap<PlayerId, GamepadHeldDirection>>,
 ) {
     use Direction::*;
     use NavRequest::{Action, Cancel, Move, ScopeMove};
 
-    let pad = Gamepad(0);
-    macro_rules! axis_delta {
-        ($dir:ident, $axis:ident) => {
-            axis.get(GamepadAxis(pad, input_mapping.$axis))
-                .map_or(Vec2::ZERO, |v| Vec2::$dir * v)
+    for input_mapping in input_mappings.iter() {
+        let pad = match input_mapping.gamepad.or_else(|| gamepads.iter().next().copied()) {
+            Some(pad) => pad,
+            None => continue,
         };
-    }
+        let held = held.entry(input_mapping.player).or_default();
+        macro_rules! axis_delta {
+            ($dir:ident, $axis:ident) => {
+                axis.get(GamepadAxis(pad, input_mapping.$axis))
+                    .map_or(Vec2::ZERO, |v| Vec2::$dir * v)
+            };
+        }
 
-    let stick_move = axis_delta!(Y, move_y) + axis_delta!(X, move_x);
-    let dpad_move = axis_delta!(Y, move_y_dpad) + axis_delta!(X, move_x_dpad);
-    let dpad_greater = dpad_move.length_squared() > stick_move.length_squared();
-    let delta = if dpad_greater { dpad_move } else { stick_move };
-    if delta.length_squared() > input_mapping.joystick_ui_deadzone && !*ui_input_status {
-        let direction = match () {
-            () if delta.y < delta.x && delta.y < -delta.x => South,
-            () if delta.y > delta.x && delta.y > -delta.x => North,
-            () if delta.y < delta.x && delta.y > -delta.x => East,
-            () if delta.y > delta.x && delta.y < -delta.x => West,
-            () => unreachable!(),
-        };
-        nav_cmds.send(Move(direction));
-        *ui_input_status = true;
-    } else if delta.length_squared() <= input_mapping.joystick_ui_deadzone {
-        *ui_input_status = false;
-    }
+        let stick_move = axis_delta!(Y, move_y) + axis_delta!(X, move_x);
+        let dpad_move = axis_delta!(Y, move_y_dpad) + axis_delta!(X, move_x_dpad);
+        let dpad_greater = dpad_move.length_squared() > stick_move.length_squared();
+        let delta = if dpad_greater { dpad_move } else { stick_move };
+        if delta.length_squared() > input_mapping.joystick_ui_deadzone {
+            let direction = match () {
+                () if delta.y < delta.x && delta.y < -delta.x => South,
+                () if delta.y > delta.x && delta.y > -delta.x => North,
+                () if delta.y < delta.x && delta.y > -delta.x => East,
+                () if delta.y > delta.x && delta.y < -delta.x => West,
+                () => unreachable!(),
+            };
+            // A dpad press switching direction is a fresh press; an analog
+            // stick drifting to a new angle while held just keeps the
+            // existing cadence.
+            let is_fresh_press =
+                held.direction.is_none() || (dpad_greater && held.direction != Some(direction));
+            if is_fresh_press {
+                nav_cmds.send(Move(input_mapping.player, direction));
+                held.timer = nav_repeat.first_delay;
+                held.interval = nav_repeat.repeat_interval;
+            } else {
+                held.timer -= time.delta_seconds();
+                if held.timer <= 0.0 {
+                    nav_cmds.send(Move(input_mapping.player, direction));
+                    held.timer = held.interval;
+                    held.interval = (held.interval * REPEAT_ACCEL).max(nav_repeat.min_interval);
+                }
+            }
+            held.direction = Some(direction);
+        } else {
+            *held = GamepadHeldDirection::default();
+        }
 
-    let command_mapping = mapping! {
-        input_mapping.action_button => Action,
-        input_mapping.cancel_button => Cancel,
-        input_mapping.next_button => ScopeMove(ScopeDirection::Next),
-        input_mapping.previous_button => ScopeMove(ScopeDirection::Previous)
-    };
-    for (key, request) in command_mapping {
-        if buttons.just_pressed(GamepadButton(pad, key)) {
-            nav_cmds.send(request)
+        let command_mapping = mapping! {
+            input_mapping.action_button => Action(input_mapping.player),
+            input_mapping.cancel_button => Cancel(input_mapping.player),
+            input_mapping.next_button => ScopeMove(input_mapping.player, ScopeDirection::Next),
+            input_mapping.previous_button => ScopeMove(input_mapping.player, ScopeDirection::Previous)
+        };
+        for (key, request) in command_mapping {
+            if buttons.just_pressed(GamepadButton(pad, key)) {
+                nav_cmds.send(request)
+            }
         }
     }
 }
 
+/// Tracks a held directional keyboard input to drive auto-repeat.
+#[derive(Default)]
+struct KeyboardHeldDirection {
+    direction: Option<Direction>,
+    timer: f32,
+    interval: f32,
+}
+
 /// A system to send keyboard control events to the focus system
 ///
 /// supports `WASD` and arrow keys for the directions, `E`, `Q` and `Tab` for
 /// scopped menus, `Backspace` and `Enter` for cancel and selection
 ///
-/// The button mapping may be controlled through the [`InputMapping`] resource.
+/// Holding a direction past [`NavRepeat::first_delay`] keeps sending
+/// [`NavRequest::Move`] every [`NavRepeat::repeat_interval`], accelerating
+/// toward [`NavRepeat::min_interval`] on each repeat. Pressing a different
+/// direction while one is already held is treated as a fresh press.
+///
+/// The button mapping and player may be controlled through each
+/// [`InputMapping`]; add one such component per local player to
+/// support couch co-op (see [`InputMapping::player`]).
 /// You may however need to customize the behavior of this system (typically
 /// when integrating in the game) in this case, you should write your own
 /// system that sends [`NavRequest`](crate::NavRequest) events
 pub fn default_keyboard_input(
     keyboard: Res<Input<KeyCode>>,
-    input_mapping: Res<InputMapping>,
+    input_mappings: Query<&InputMapping>,
+    nav_repeat: Res<NavRepeat>,
+    time: Res<Time>,
     mut nav_cmds: EventWriter<NavRequest>,
+    mut held: Local<HashMap<PlayerId, KeyboardHeldDirection>>,
 ) {
     use Direction::*;
     use NavRequest::*;
 
-    let command_mapping = mapping! {
-        input_mapping.key_action => Action,
-        input_mapping.key_cancel => Cancel,
-        input_mapping.key_up => Move(North),
-        input_mapping.key_down => Move(South),
-        input_mapping.key_left => Move(West),
-        input_mapping.key_right => Move(East),
-        input_mapping.key_up_alt => Move(North),
-        input_mapping.key_down_alt => Move(South),
-        input_mapping.key_left_alt => Move(West),
-        input_mapping.key_right_alt => Move(East),
-        input_mapping.key_next => ScopeMove(ScopeDirection::Next),
-        input_mapping.key_next_alt => ScopeMove(ScopeDirection::Next),
-        input_mapping.key_previous => ScopeMove(ScopeDirection::Previous)
-    };
-    for (key, request) in command_mapping {
-        if keyboard.just_pressed(key) {
-            nav_cmds.send(request)
+    for input_mapping in input_mappings.iter() {
+        let player = input_mapping.player;
+        let command_mapping = mapping! {
+            input_mapping.key_action => Action(player),
+            input_mapping.key_cancel => Cancel(player),
+            input_mapping.key_next => ScopeMove(player, ScopeDirection::Next),
+            input_mapping.key_next_alt => ScopeMove(player, ScopeDirection::Next),
+            input_mapping.key_previous => ScopeMove(player, ScopeDirection::Previous)
+        };
+        for (key, request) in command_mapping {
+            if keyboard.just_pressed(key) {
+                nav_cmds.send(request)
+            }
+        }
+
+        let direction_mapping = mapping! {
+            input_mapping.key_up => North,
+            input_mapping.key_up_alt => North,
+            input_mapping.key_down => South,
+            input_mapping.key_down_alt => South,
+            input_mapping.key_left => West,
+            input_mapping.key_left_alt => West,
+            input_mapping.key_right => East,
+            input_mapping.key_right_alt => East
+        };
+        let fresh_direction = direction_mapping
+            .iter()
+            .find(|(key, _)| keyboard.just_pressed(*key))
+            .map(|(_, direction)| *direction);
+        let held = held.entry(player).or_default();
+        // If several direction keys are held at once, keep repeating whichever
+        // one was already being repeated instead of flickering to whatever
+        // key happens to come first in `direction_mapping`.
+        let held_direction = held
+            .direction
+            .filter(|direction| {
+                direction_mapping
+                    .iter()
+                    .any(|(key, held)| held == direction && keyboard.pressed(*key))
+            })
+            .or_else(|| {
+                direction_mapping
+                    .iter()
+                    .find(|(key, _)| keyboard.pressed(*key))
+                    .map(|(_, direction)| *direction)
+            });
+
+        if let Some(direction) = fresh_direction {
+            nav_cmds.send(Move(player, direction));
+            held.direction = Some(direction);
+            held.timer = nav_repeat.first_delay;
+            held.interval = nav_repeat.repeat_interval;
+        } else if let Some(direction) = held_direction {
+            held.timer -= time.delta_seconds();
+            if held.timer <= 0.0 {
+                nav_cmds.send(Move(player, direction));
+                held.timer = held.interval;
+                held.interval = (held.interval * REPEAT_ACCEL).max(nav_repeat.min_interval);
+            }
+        } else {
+            *held = KeyboardHeldDirection::default();
         }
     }
 }
@@ -235,15 +370,21 @@ fn cursor_pos(windows: &Windows) -> Option<Vec2> {
 /// [`ui_focusable_at`] to tell which focusable is currently being hovered.
 #[allow(clippy::too_many_arguments)]
 pub fn default_mouse_input(
-    input_mapping: Res<InputMapping>,
+    input_mappings: Query<&InputMapping>,
     windows: Res<Windows>,
     mouse: Res<Input<MouseButton>>,
     touch: Res<Touches>,
     focusables: NodePosQuery,
-    focused: Query<Entity, With<Focused>>,
+    focused: Query<(Entity, &Focused)>,
     mut nav_cmds: EventWriter<NavRequest>,
     mut last_pos: Local<Vec2>,
 ) {
+    // The pointer isn't per-player: whichever `InputMapping` is registered
+    // first drives mouse/touch focus.
+    let input_mapping = match input_mappings.iter().next() {
+        Some(input_mapping) => input_mapping,
+        None => return,
+    };
     let ui_cam_name = focusables
         .cam_names
         .iter()
@@ -258,7 +399,10 @@ pub fn default_mouse_input(
         None => return,
     };
     let released = mouse.just_released(input_mapping.mouse_action) || touch.just_released(0);
-    let focused = focused.get_single();
+    let focused = focused
+        .iter()
+        .find(|(_, focused)| focused.0 == input_mapping.player)
+        .map(|(entity, _)| entity);
     // Return early if cursor didn't move since last call
     if !released && *last_pos == cursor_pos {
         return;
@@ -281,8 +425,477 @@ pub fn default_mouse_input(
             Some(c) => c,
             None => return,
         };
-        nav_cmds.send(NavRequest::FocusOn(to_target));
+        nav_cmds.send(NavRequest::FocusOn(
+            input_mapping.player,
+            to_target,
+            FocusSource::Pointer,
+        ));
     } else if released {
-        nav_cmds.send(NavRequest::Action);
+        nav_cmds.send(NavRequest::Action(input_mapping.player));
+    }
+}
+
+/// The unit vector a [`Direction`] points toward, in `GlobalTransform` space.
+fn direction_vector(direction: Direction) -> Vec2 {
+    match direction {
+        Direction::North => Vec2::new(0.0, 1.0),
+        Direction::South => Vec2::new(0.0, -1.0),
+        Direction::East => Vec2::new(1.0, 0.0),
+        Direction::West => Vec2::new(-1.0, 0.0),
+    }
+}
+
+/// Picks the best candidate ahead of `from` in `direction`, among
+/// `candidates`, favoring whichever is closest while penalizing candidates
+/// that are off to the side.
+fn closest_neighbor(
+    from: Vec2,
+    direction: Direction,
+    candidates: impl Iterator<Item = (Entity, Vec2)>,
+) -> Option<Entity> {
+    let dir_vec = direction_vector(direction);
+    candidates
+        .filter_map(|(entity, pos)| {
+            let relative = pos - from;
+            let forward = relative.dot(dir_vec);
+            (forward > 0.0).then(|| {
+                let sideways = relative - dir_vec * forward;
+                (entity, forward + sideways.length() * 2.0)
+            })
+        })
+        .fold(None, |best, candidate| match best {
+            Some((_, best_score)) if best_score <= candidate.1 => best,
+            _ => Some(candidate),
+        })
+        .map(|(entity, _)| entity)
+}
+
+/// Picks the farthest candidate behind `from` along `direction`'s axis (ie.
+/// on the opposite edge of the menu), tie-broken by whichever is nearest on
+/// the perpendicular axis. Used by [`resolve_focus`] to wrap navigation that
+/// dead-ended at a [`NavMenu`]'s edge.
+fn wrap_neighbor(
+    from: Vec2,
+    direction: Direction,
+    candidates: impl Iterator<Item = (Entity, Vec2)>,
+) -> Option<Entity> {
+    let dir_vec = direction_vector(direction);
+    candidates
+        .map(|(entity, pos)| {
+            let relative = pos - from;
+            let along = relative.dot(dir_vec);
+            let sideways = (relative - dir_vec * along).length();
+            (entity, along, sideways)
+        })
+        .fold(None, |best, candidate| match best {
+            Some((_, best_along, best_sideways))
+                if best_along < candidate.1
+                    || (best_along == candidate.1 && best_sideways <= candidate.2) =>
+            {
+                best
+            }
+            _ => Some(candidate),
+        })
+        .map(|(entity, ..)| entity)
+}
+
+/// The system resolving [`NavRequest`]s into focus changes, reading input
+/// events sent by eg. [`default_keyboard_input`] and emitting [`NavEvent`].
+///
+/// Added automatically by [`crate::NavigationPlugin`].
+pub fn resolve_focus(
+    mut commands: Commands,
+    mut requests: EventReader<NavRequest>,
+    mut events: EventWriter<NavEvent>,
+    focusables: Query<(Entity, &GlobalTransform, &Focusable)>,
+    focused: Query<(Entity, &Focused)>,
+    parents: Query<&Parent>,
+    menus: Query<&NavMenu>,
+) {
+    // Tracks focus as requests are processed, rather than the `focused` query
+    // snapshotted at the top of this system: several players' requests can
+    // land in the same batch, and each must see the others' claims so they
+    // don't grab (or steal back) the same entity.
+    let mut focus_of: HashMap<PlayerId, Entity> =
+        focused.iter().map(|(entity, focused)| (focused.0, entity)).collect();
+    let mut claimed_by: HashMap<Entity, PlayerId> =
+        focus_of.iter().map(|(&player, &entity)| (entity, player)).collect();
+
+    // Moves `player`'s focus to `entity`, releasing whatever it held before.
+    let claim = |commands: &mut Commands,
+                      focus_of: &mut HashMap<PlayerId, Entity>,
+                      claimed_by: &mut HashMap<Entity, PlayerId>,
+                      player: PlayerId,
+                      entity: Entity| {
+        if let Some(previous) = focus_of.insert(player, entity) {
+            if previous != entity {
+                commands.entity(previous).remove::<Focused>();
+                claimed_by.remove(&previous);
+            }
+        }
+        commands.entity(entity).insert(Focused(player));
+        claimed_by.insert(entity, player);
+    };
+
+    for request in requests.iter() {
+        match *request {
+            NavRequest::Move(player, direction) => {
+                let current = match focus_of.get(&player).copied() {
+                    Some(current) => current,
+                    None => {
+                        let first = focusables.iter().find_map(|(entity, ..)| {
+                            let unclaimed = !claimed_by.contains_key(&entity);
+                            let in_scope = match menu_player(entity, &parents, &menus) {
+                                None => true,
+                                Some(owner) => owner == player,
+                            };
+                            (unclaimed && in_scope).then(|| entity)
+                        });
+                        if let Some(first) = first {
+                            claim(&mut commands, &mut focus_of, &mut claimed_by, player, first);
+                            events.send(NavEvent::FocusChanged {
+                                player,
+                                from: first,
+                                to: first,
+                                source: FocusSource::Directional,
+                            });
+                        }
+                        continue;
+                    }
+                };
+                let (current_pos, adjust_lock) = match focusables.get(current) {
+                    Ok((_, transform, focusable)) => {
+                        (transform.translation.xy(), focusable.adjust_lock)
+                    }
+                    Err(_) => continue,
+                };
+                if adjust_lock == Some(direction.axis()) {
+                    events.send(NavEvent::Adjusted {
+                        player,
+                        entity: current,
+                        direction,
+                    });
+                    continue;
+                }
+                let current_menu = enclosing_menu(current, &parents, &menus);
+                let is_sibling = |entity: Entity| {
+                    entity != current
+                        && enclosing_menu(entity, &parents, &menus) == current_menu
+                        && claimed_by.get(&entity).map_or(true, |&owner| owner == player)
+                };
+                let candidates = focusables.iter().filter_map(|(entity, transform, _)| {
+                    is_sibling(entity).then(|| (entity, transform.translation.xy()))
+                });
+                match closest_neighbor(current_pos, direction, candidates) {
+                    Some(next) => {
+                        claim(&mut commands, &mut focus_of, &mut claimed_by, player, next);
+                        events.send(NavEvent::FocusChanged {
+                            player,
+                            from: current,
+                            to: next,
+                            source: FocusSource::Directional,
+                        });
+                    }
+                    None => {
+                        let wraps = current_menu
+                            .and_then(|menu| menus.get(menu).ok())
+                            .map_or(false, |menu| match direction.axis() {
+                                crate::Axis::Horizontal => menu.wrap.horizontal,
+                                crate::Axis::Vertical => menu.wrap.vertical,
+                            });
+                        let wrapped = wraps
+                            .then(|| {
+                                let candidates =
+                                    focusables.iter().filter_map(|(entity, transform, _)| {
+                                        is_sibling(entity).then(|| (entity, transform.translation.xy()))
+                                    });
+                                wrap_neighbor(current_pos, direction, candidates)
+                            })
+                            .flatten();
+                        match wrapped {
+                            Some(next) => {
+                                claim(&mut commands, &mut focus_of, &mut claimed_by, player, next);
+                                events.send(NavEvent::FocusChanged {
+                                    player,
+                                    from: current,
+                                    to: next,
+                                    source: FocusSource::Directional,
+                                });
+                            }
+                            None => events.send(NavEvent::NoChanges {
+                                player,
+                                from: current,
+                                request: *request,
+                            }),
+                        }
+                    }
+                }
+            }
+            NavRequest::ScopeMove(player, scope_direction) => {
+                let current = match focus_of.get(&player).copied() {
+                    Some(current) => current,
+                    None => continue,
+                };
+                let current_menu = enclosing_menu(current, &parents, &menus);
+                let mut siblings: Vec<_> = focusables
+                    .iter()
+                    .filter(|(entity, ..)| {
+                        enclosing_menu(*entity, &parents, &menus) == current_menu
+                            && claimed_by.get(entity).map_or(true, |&owner| owner == player)
+                    })
+                    .map(|(entity, transform, _)| (entity, transform.translation.x))
+                    .collect();
+                siblings.sort_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap());
+                if let Some(position) = siblings.iter().position(|(entity, _)| *entity == current) {
+                    let len = siblings.len();
+                    let next_position = match scope_direction {
+                        ScopeDirection::Next => (position + 1) % len,
+                        ScopeDirection::Previous => (position + len - 1) % len,
+                    };
+                    let next = siblings[next_position].0;
+                    if next != current {
+                        claim(&mut commands, &mut focus_of, &mut claimed_by, player, next);
+                        events.send(NavEvent::FocusChanged {
+                            player,
+                            from: current,
+                            to: next,
+                            source: FocusSource::Scope,
+                        });
+                    }
+                }
+            }
+            NavRequest::Action(player) => {
+                if let Some(current) = focus_of.get(&player).copied() {
+                    events.send(NavEvent::FocusActivated(player, current));
+                }
+            }
+            // Cancelling is entirely up to the consuming game (eg. closing a
+            // submenu), so there is nothing for the focus system to do here.
+            NavRequest::Cancel(_) => {}
+            NavRequest::FocusOn(player, target, source) => {
+                if focusables.get(target).is_err() {
+                    continue;
+                }
+                // Don't steal an entity another player's cursor is on.
+                if claimed_by.get(&target).map_or(false, |&owner| owner != player) {
+                    continue;
+                }
+                let current = focus_of.get(&player).copied();
+                claim(&mut commands, &mut focus_of, &mut claimed_by, player, target);
+                events.send(NavEvent::FocusChanged {
+                    player,
+                    from: current.unwrap_or(target),
+                    to: target,
+                    source,
+                });
+            }
+        }
+    }
+}
+
+/// Names a single [`InputMapping`] field that a [`PendingRebind`] targets.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RebindTarget {
+    ActionButton,
+    CancelButton,
+    PreviousButton,
+    NextButton,
+    KeyUp,
+    KeyDown,
+    KeyLeft,
+    KeyRight,
+    KeyUpAlt,
+    KeyDownAlt,
+    KeyLeftAlt,
+    KeyRightAlt,
+    KeyAction,
+    KeyCancel,
+    KeyNext,
+    KeyNextAlt,
+    KeyPrevious,
+    MouseAction,
+    MoveX,
+    MoveY,
+    MoveXDpad,
+    MoveYDpad,
+}
+impl RebindTarget {
+    /// The gamepad axes this target may capture a rebind from, if it's an
+    /// axis target at all: dpad targets only match the dpad's axes, and
+    /// `MoveX`/`MoveY` only match the matching orientation on either stick.
+    /// Keeps rebinding e.g. `MoveYDpad` from picking up unrelated drift on
+    /// the left stick.
+    fn axis_candidates(self) -> Option<&'static [GamepadAxisType]> {
+        use GamepadAxisType::*;
+        match self {
+            RebindTarget::MoveX => Some(&[LeftStickX, RightStickX]),
+            RebindTarget::MoveY => Some(&[LeftStickY, RightStickY]),
+            RebindTarget::MoveXDpad => Some(&[DPadX]),
+            RebindTarget::MoveYDpad => Some(&[DPadY]),
+            _ => None,
+        }
+    }
+}
+
+/// Insert this resource to put [`rebind_input`] into listening mode: the
+/// next matching input is written into the `target` field of whichever
+/// [`InputMapping`] belongs to `player`.
+///
+/// `ignore_first_frame` should usually be `true` when the rebind is opened in
+/// response to an input (such as clicking a "rebind" button), so that same
+/// input isn't immediately captured as the new binding.
+pub struct PendingRebind {
+    /// Which player's `InputMapping` to rebind.
+    pub player: PlayerId,
+    /// The `InputMapping` field to overwrite.
+    pub target: RebindTarget,
+    /// Skips capturing on the first frame this resource exists.
+    pub ignore_first_frame: bool,
+}
+
+/// Sent by [`rebind_input`] once a [`PendingRebind`] has been fulfilled.
+pub struct RebindComplete {
+    /// The `InputMapping` field that was just overwritten.
+    pub target: RebindTarget,
+}
+
+/// Minimum absolute axis value considered a deliberate rebind input.
+const REBIND_AXIS_THRESHOLD: f32 = 0.5;
+
+/// Scans `candidates` for the first one deflected past
+/// [`REBIND_AXIS_THRESHOLD`]. A deflection only commits once the same axis
+/// and sign has been seen on two consecutive calls, debouncing a single
+/// noisy or drifting frame; `last_capture` remembers the previous call's
+/// not-yet-committed candidate, and is cleared whenever a call sees no
+/// deflection or a different one.
+fn first_deflected_axis(
+    pad: Gamepad,
+    axis: &Axis<GamepadAxis>,
+    candidates: &[GamepadAxisType],
+    last_capture: &mut Option<(GamepadAxisType, bool)>,
+) -> Option<GamepadAxisType> {
+    let deflected = candidates.iter().find_map(|&axis_type| {
+        let value = axis.get(GamepadAxis(pad, axis_type)).unwrap_or(0.0);
+        (value.abs() >= REBIND_AXIS_THRESHOLD).then(|| (axis_type, value > 0.0))
+    });
+    match (deflected, *last_capture) {
+        (Some(seen), Some(previous)) if seen == previous => {
+            *last_capture = None;
+            Some(seen.0)
+        }
+        (Some(seen), _) => {
+            *last_capture = Some(seen);
+            None
+        }
+        (None, _) => {
+            *last_capture = None;
+            None
+        }
+    }
+}
+
+/// A system to support a runtime "press a key to rebind" controls menu.
+///
+/// While a [`PendingRebind`] resource is present, this scans keyboard,
+/// gamepad buttons, gamepad axes and mouse buttons for the first newly
+/// pressed input, writes it into the [`InputMapping`] field named by
+/// [`PendingRebind::target`] on [`PendingRebind::player`]'s mapping, removes
+/// the `PendingRebind` resource and emits [`RebindComplete`].
+pub fn rebind_input(
+    mut commands: Commands,
+    pending: Option<ResMut<PendingRebind>>,
+    mut input_mappings: Query<&mut InputMapping>,
+    keyboard: Res<Input<KeyCode>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mouse: Res<Input<MouseButton>>,
+    axis: Res<Axis<GamepadAxis>>,
+    gamepads: Res<Gamepads>,
+    mut rebind_complete: EventWriter<RebindComplete>,
+    mut last_axis_capture: Local<Option<(GamepadAxisType, bool)>>,
+) {
+    let mut pending = match pending {
+        Some(pending) => pending,
+        None => {
+            *last_axis_capture = None;
+            return;
+        }
+    };
+    if pending.ignore_first_frame {
+        pending.ignore_first_frame = false;
+        return;
+    }
+    let mut input_mapping = match input_mappings
+        .iter_mut()
+        .find(|mapping| mapping.player == pending.player)
+    {
+        Some(input_mapping) => input_mapping,
+        None => return,
+    };
+    let target = pending.target;
+    let pad = input_mapping.gamepad.or_else(|| gamepads.iter().next().copied());
+
+    macro_rules! capture_key {
+        ($field:ident) => {
+            keyboard.get_just_pressed().next().map(|&key| {
+                input_mapping.$field = key;
+            })
+        };
+    }
+    macro_rules! capture_button {
+        ($field:ident) => {
+            pad.and_then(|pad| {
+                gamepad_buttons
+                    .get_just_pressed()
+                    .find(|button| button.0 == pad)
+                    .map(|button| {
+                        input_mapping.$field = button.1;
+                    })
+            })
+        };
+    }
+    let captured = match target.axis_candidates() {
+        Some(candidates) => pad.and_then(|pad| {
+            first_deflected_axis(pad, &axis, candidates, &mut last_axis_capture).map(
+                |axis_type| match target {
+                    RebindTarget::MoveX => input_mapping.move_x = axis_type,
+                    RebindTarget::MoveY => input_mapping.move_y = axis_type,
+                    RebindTarget::MoveXDpad => input_mapping.move_x_dpad = axis_type,
+                    RebindTarget::MoveYDpad => input_mapping.move_y_dpad = axis_type,
+                    _ => unreachable!("axis_candidates() is Some only for axis targets"),
+                },
+            )
+        }),
+        None => match target {
+            RebindTarget::ActionButton => capture_button!(action_button),
+            RebindTarget::CancelButton => capture_button!(cancel_button),
+            RebindTarget::PreviousButton => capture_button!(previous_button),
+            RebindTarget::NextButton => capture_button!(next_button),
+            RebindTarget::KeyUp => capture_key!(key_up),
+            RebindTarget::KeyDown => capture_key!(key_down),
+            RebindTarget::KeyLeft => capture_key!(key_left),
+            RebindTarget::KeyRight => capture_key!(key_right),
+            RebindTarget::KeyUpAlt => capture_key!(key_up_alt),
+            RebindTarget::KeyDownAlt => capture_key!(key_down_alt),
+            RebindTarget::KeyLeftAlt => capture_key!(key_left_alt),
+            RebindTarget::KeyRightAlt => capture_key!(key_right_alt),
+            RebindTarget::KeyAction => capture_key!(key_action),
+            RebindTarget::KeyCancel => capture_key!(key_cancel),
+            RebindTarget::KeyNext => capture_key!(key_next),
+            RebindTarget::KeyNextAlt => capture_key!(key_next_alt),
+            RebindTarget::KeyPrevious => capture_key!(key_previous),
+            RebindTarget::MouseAction => mouse.get_just_pressed().next().map(|&button| {
+                input_mapping.mouse_action = button;
+            }),
+            RebindTarget::MoveX
+            | RebindTarget::MoveY
+            | RebindTarget::MoveXDpad
+            | RebindTarget::MoveYDpad => unreachable!("axis_candidates() is None only for non-axis targets"),
+        },
+    }
+    .is_some();
+
+    if captured {
+        commands.remove_resource::<PendingRebind>();
+        rebind_complete.send(RebindComplete { target });
     }
 }